@@ -23,27 +23,149 @@ pub fn format_commit_line_with_osc8_commit_hyperlink<'a>(
     line: &'a str,
     config: &Config,
 ) -> Cow<'a, str> {
-    if let Some(commit_link_format) = &config.hyperlinks_commit_link_format {
-        COMMIT_LINE_REGEX.replace(line, |captures: &Captures| {
-            let commit = captures.get(2).unwrap().as_str();
-            format_osc8_hyperlink(&commit_link_format.replace("{commit}", commit), commit)
-        })
-    } else if let Some(GitConfigEntry::GitRemote(GitRemoteRepo::GitHubRepo(repo))) =
-        config.git_config.as_ref().and_then(get_remote_url)
-    {
-        COMMIT_LINE_REGEX.replace(line, |captures: &Captures| {
-            format_commit_line_captures_with_osc8_commit_hyperlink(captures, &repo)
+    let remote = remote_repo(config);
+    let line = format_commit_hash_with_osc8_hyperlink(line, remote.as_ref(), config);
+    match format_issue_references_with_osc8_hyperlink(&line, remote.as_ref(), config) {
+        Cow::Borrowed(_) => line,
+        Cow::Owned(s) => Cow::Owned(s),
+    }
+}
+
+fn format_commit_hash_with_osc8_hyperlink<'a>(
+    line: &'a str,
+    remote: Option<&GitRemoteRepo>,
+    config: &Config,
+) -> Cow<'a, str> {
+    let commit_match = match find_commit_hash(line) {
+        Some(m) => m,
+        None => return Cow::from(line),
+    };
+    let commit = commit_match.as_str();
+    let url = if let Some(commit_link_format) = &config.hyperlinks_commit_link_format {
+        commit_link_format.replace("{commit}", commit)
+    } else if let Some(remote) = remote {
+        remote.format_commit_url(commit)
+    } else {
+        return Cow::from(line);
+    };
+    let mut result = String::with_capacity(line.len() + url.len());
+    result.push_str(&line[..commit_match.start()]);
+    result.push_str(&format_osc8_hyperlink(&url, commit));
+    result.push_str(&line[commit_match.end()..]);
+    Cow::Owned(result)
+}
+
+/// Find the leftmost run of 7-40 hex characters in `line` that looks like
+/// a commit hash rather than a plain decimal number (version, count,
+/// etc.), by requiring at least one `a`-`f` character. Plain `.*`-wrapped
+/// scanning would otherwise let a later all-decimal token win via
+/// backtracking, e.g. in `"abc1234d Bump build number to 1234567"` it
+/// would match `1234567` instead of the actual hash `abc1234d`.
+fn find_commit_hash(line: &str) -> Option<regex::Match> {
+    COMMIT_HASH_REGEX
+        .find_iter(line)
+        .find(|m| m.as_str().bytes().any(|b| (b'a'..=b'f').contains(&b)))
+}
+
+/// A second pass over `line`, linking `#123`, `GH-123`, and `owner/repo#123`
+/// issue/PR references to the forge's issue tracker. Runs after commit-hash
+/// linking, and is careful not to re-link text that is already inside an
+/// OSC8 hyperlink emitted by that first pass.
+fn format_issue_references_with_osc8_hyperlink<'a>(
+    line: &'a str,
+    remote: Option<&GitRemoteRepo>,
+    config: &Config,
+) -> Cow<'a, str> {
+    if !ISSUE_REF_REGEX.is_match(line) {
+        return Cow::from(line);
+    }
+    if config.hyperlinks_issue_link_format.is_none() && remote.is_none() {
+        return Cow::from(line);
+    }
+    let mut result = String::with_capacity(line.len());
+    let mut last_end = 0;
+    for osc8_match in OSC8_HYPERLINK_REGEX.find_iter(line) {
+        result.push_str(&link_issue_references(
+            &line[last_end..osc8_match.start()],
+            config,
+            remote,
+        ));
+        result.push_str(osc8_match.as_str());
+        last_end = osc8_match.end();
+    }
+    result.push_str(&link_issue_references(
+        &line[last_end..],
+        config,
+        remote,
+    ));
+    Cow::Owned(result)
+}
+
+fn link_issue_references(segment: &str, config: &Config, remote: Option<&GitRemoteRepo>) -> String {
+    ISSUE_REF_REGEX
+        .replace_all(segment, |captures: &Captures| {
+            format_issue_reference_captures_with_osc8_hyperlink(captures, config, remote)
         })
+        .into_owned()
+}
+
+fn format_issue_reference_captures_with_osc8_hyperlink(
+    captures: &Captures,
+    config: &Config,
+    remote: Option<&GitRemoteRepo>,
+) -> String {
+    let text = captures.get(0).unwrap().as_str();
+    let (owner_repo, number) = issue_reference_parts(captures);
+    let url = if let Some(issue_link_format) = &config.hyperlinks_issue_link_format {
+        let mut url = issue_link_format.replace("{number}", number);
+        if let Some((owner, repo)) = owner_repo {
+            url = url.replace("{owner}", owner).replace("{repo}", repo);
+        }
+        url
+    } else if let Some(remote) = remote {
+        match owner_repo {
+            Some((owner, repo)) => remote.format_issue_url_for(owner, repo, number),
+            None => remote.format_issue_url(number),
+        }
     } else {
-        Cow::from(line)
+        return text.to_string();
+    };
+    format_osc8_hyperlink(&url, text)
+}
+
+/// Extract the `(owner, repo)` (if the reference named a specific repo) and
+/// issue number from a match of `ISSUE_REF_REGEX`.
+fn issue_reference_parts<'t>(captures: &Captures<'t>) -> (Option<(&'t str, &'t str)>, &'t str) {
+    if let Some(number) = captures.name("cross_repo_number") {
+        let owner = captures.name("owner").unwrap().as_str();
+        let repo = captures.name("repo").unwrap().as_str();
+        (Some((owner, repo)), number.as_str())
+    } else if let Some(number) = captures.name("gh_number") {
+        (None, number.as_str())
+    } else {
+        (None, captures.name("number").unwrap().as_str())
     }
 }
 
-fn get_remote_url(git_config: &GitConfig) -> Option<GitConfigEntry> {
-    git_config
-        .repo
-        .as_ref()?
-        .find_remote("origin")
+fn remote_repo(config: &Config) -> Option<GitRemoteRepo> {
+    match config
+        .git_config
+        .as_ref()
+        .and_then(|git_config| get_remote_url(git_config, config))
+    {
+        Some(GitConfigEntry::GitRemote(remote)) => Some(remote),
+        _ => None,
+    }
+}
+
+fn get_remote_url(git_config: &GitConfig, config: &Config) -> Option<GitConfigEntry> {
+    let repo = git_config.repo.as_ref()?;
+    let remote_name = config
+        .hyperlinks_commit_link_remote
+        .clone()
+        .or_else(|| tracked_upstream_remote_name(repo))
+        .unwrap_or_else(|| "origin".to_string());
+    repo.find_remote(&remote_name)
         .ok()?
         .url()
         .and_then(|url| {
@@ -53,30 +175,76 @@ fn get_remote_url(git_config: &GitConfig) -> Option<GitConfigEntry> {
         })
 }
 
-/// Create a file hyperlink to `path`, displaying `text`.
+/// The remote that the current branch is configured to track
+/// (`branch.<name>.remote`), or `None` if there is no such configuration,
+/// e.g. on a detached `HEAD` or an untracked local branch.
+fn tracked_upstream_remote_name(repo: &git2::Repository) -> Option<String> {
+    let branch_name = repo.head().ok()?.shorthand()?.to_string();
+    repo.branch_upstream_remote(&format!("refs/heads/{}", branch_name))
+        .ok()
+        .and_then(|buf| buf.as_str().map(str::to_string))
+}
+
+/// Create a file hyperlink to `path`, displaying `text`. `line_range`, if
+/// given, is the `(start, end)` lines to link to; `start == end` for a
+/// single-line link. Templates may reference the start via `{line}` and
+/// the end via `{line_end}`, or use the forge-aware `{line_range}`
+/// placeholder to get a single anchor in the target forge's own style
+/// (e.g. GitHub's `#L10-L25` vs GitLab's `#L10-25`).
 pub fn format_osc8_file_hyperlink<'a>(
     relative_path: &'a str,
-    line_number: Option<usize>,
+    line_range: Option<(usize, usize)>,
     text: &str,
     config: &Config,
 ) -> Cow<'a, str> {
     if let Some(GitConfigEntry::Path(workdir)) = config.git_config_entries.get("delta.__workdir__")
     {
         let absolute_path = workdir.join(relative_path);
-        let mut url = config
-            .hyperlinks_file_link_format
-            .replace("{path}", &absolute_path.to_string_lossy());
-        if let Some(n) = line_number {
-            url = url.replace("{line}", &format!("{}", n))
-        } else {
-            url = url.replace("{line}", "")
-        };
+        let url = build_file_link_url(
+            &config.hyperlinks_file_link_format,
+            &absolute_path.to_string_lossy(),
+            line_range,
+            remote_repo(config).as_ref(),
+        );
         Cow::from(format_osc8_hyperlink(&url, text))
     } else {
         Cow::from(relative_path)
     }
 }
 
+/// Substitute `{path}`, `{line}`, `{line_end}`, and `{line_range}` into a
+/// `hyperlinks-file-link-format` template. `{line_range}` resolves through
+/// `remote`'s forge-specific anchor style when a remote is available,
+/// falling back to GitHub's `#L{start}-L{end}` style otherwise.
+fn build_file_link_url(
+    template: &str,
+    path: &str,
+    line_range: Option<(usize, usize)>,
+    remote: Option<&GitRemoteRepo>,
+) -> String {
+    let mut url = template.replace("{path}", path);
+    match line_range {
+        Some((start, end)) => {
+            url = url
+                .replace("{line}", &start.to_string())
+                .replace("{line_end}", &end.to_string());
+            if url.contains("{line_range}") {
+                let anchor = remote
+                    .map(|remote| remote.format_line_range_anchor(start, end))
+                    .unwrap_or_else(|| format!("#L{}-L{}", start, end));
+                url = url.replace("{line_range}", &anchor);
+            }
+        }
+        None => {
+            url = url
+                .replace("{line}", "")
+                .replace("{line_end}", "")
+                .replace("{line_range}", "");
+        }
+    }
+    url
+}
+
 fn format_osc8_hyperlink(url: &str, text: &str) -> String {
     format!(
         "{osc}8;;{url}{st}{text}{osc}8;;{st}",
@@ -88,27 +256,22 @@ fn format_osc8_hyperlink(url: &str, text: &str) -> String {
 }
 
 lazy_static! {
-    static ref COMMIT_LINE_REGEX: Regex = Regex::new("(.* )([0-9a-f]{40})(.*)").unwrap();
-}
+    // Abbreviated hashes are as short as 7 characters (git's default),
+    // up to a full 40-character SHA-1. A bare `[0-9a-f]{7,40}` would also
+    // match plain decimal tokens (version numbers, counts, ...); the
+    // `find_commit_hash` caller additionally requires an `a`-`f` digit.
+    static ref COMMIT_HASH_REGEX: Regex = Regex::new(r"\b[0-9a-f]{7,40}\b").unwrap();
 
-fn format_commit_line_captures_with_osc8_commit_hyperlink(
-    captures: &Captures,
-    github_repo: &str,
-) -> String {
-    let commit = captures.get(2).unwrap().as_str();
-    format!(
-        "{prefix}{osc}8;;{url}{st}{commit}{osc}8;;{st}{suffix}",
-        url = format_github_commit_url(commit, github_repo),
-        commit = commit,
-        prefix = captures.get(1).unwrap().as_str(),
-        suffix = captures.get(3).unwrap().as_str(),
-        osc = "\x1b]",
-        st = "\x1b\\"
-    )
-}
+    // `#123`, `GH-123`, or `owner/repo#123`.
+    static ref ISSUE_REF_REGEX: Regex = Regex::new(
+        r"(?P<owner>[A-Za-z0-9_.-]+)/(?P<repo>[A-Za-z0-9_.-]+)#(?P<cross_repo_number>\d+)|\bGH-(?P<gh_number>\d+)|#(?P<number>\d+)"
+    ).unwrap();
 
-fn format_github_commit_url(commit: &str, github_repo: &str) -> String {
-    format!("https://github.com/{}/commit/{}", github_repo, commit)
+    // An OSC8 hyperlink as emitted by `format_osc8_hyperlink`, i.e.
+    // `ESC]8;;{url}ESC\{text}ESC]8;;ESC\`. Used to avoid re-linking text
+    // that a previous pass has already wrapped in a hyperlink.
+    static ref OSC8_HYPERLINK_REGEX: Regex =
+        Regex::new("\x1b\\]8;;[^\x1b]*\x1b\\\\[^\x1b]*\x1b\\]8;;\x1b\\\\").unwrap();
 }
 
 #[cfg(test)]
@@ -118,6 +281,53 @@ mod tests {
     use super::format_commit_line_with_osc8_commit_hyperlink;
     use crate::tests::integration_test_utils;
 
+    #[test]
+    fn test_build_file_link_url_substitutes_line_and_line_end() {
+        use super::build_file_link_url;
+
+        let url = build_file_link_url(
+            "file://{path}#L{line}-L{line_end}",
+            "/abs/src/main.rs",
+            Some((10, 25)),
+            None,
+        );
+        assert_eq!(url, "file:///abs/src/main.rs#L10-L25");
+    }
+
+    #[test]
+    fn test_build_file_link_url_line_range_placeholder_without_remote() {
+        use super::build_file_link_url;
+
+        let url = build_file_link_url("{path}{line_range}", "src/main.rs", Some((10, 25)), None);
+        assert_eq!(url, "src/main.rs#L10-L25");
+    }
+
+    #[test]
+    fn test_build_file_link_url_line_range_placeholder_is_forge_aware() {
+        use std::str::FromStr;
+
+        use super::build_file_link_url;
+        use crate::git_config::GitRemoteRepo;
+
+        let remote = GitRemoteRepo::from_str("https://gitlab.com/owner/repo").unwrap();
+        let url = build_file_link_url(
+            "{path}{line_range}",
+            "src/main.rs",
+            Some((10, 25)),
+            Some(&remote),
+        );
+        assert_eq!(url, "src/main.rs#L10-25");
+    }
+
+    #[test]
+    fn test_find_commit_hash_ignores_trailing_decimal_number() {
+        use super::find_commit_hash;
+
+        let line = "abc1234d Bump build number to 1234567";
+        let commit_match = find_commit_hash(line).unwrap();
+        assert_eq!(commit_match.as_str(), "abc1234d");
+    }
+
     #[test]
     fn test_commit_hyperlink_honors_insteadof() {
         let git_config_contents = br#"
@@ -150,4 +360,40 @@ mod tests {
 
         remove_file(git_config_path).unwrap();
     }
+
+    #[test]
+    fn test_issue_reference_linking_with_explicit_format() {
+        use super::format_issue_references_with_osc8_hyperlink;
+        use crate::tests::integration_test_utils;
+
+        let config = integration_test_utils::make_config_from_args_and_git_config(
+            &[
+                "--hyperlinks-issue-link-format",
+                "https://example.com/issues/{number}",
+            ],
+            None,
+            None,
+        );
+        let line = "See #123 for details";
+        let formatted = format_issue_references_with_osc8_hyperlink(line, None, &config);
+        assert!(formatted.contains("https://example.com/issues/123"));
+    }
+
+    #[test]
+    fn test_cross_repo_issue_reference_linking_with_explicit_format() {
+        use super::format_issue_references_with_osc8_hyperlink;
+        use crate::tests::integration_test_utils;
+
+        let config = integration_test_utils::make_config_from_args_and_git_config(
+            &[
+                "--hyperlinks-issue-link-format",
+                "https://example.com/{owner}/{repo}/issues/{number}",
+            ],
+            None,
+            None,
+        );
+        let line = "See dandavison/delta#456 for details";
+        let formatted = format_issue_references_with_osc8_hyperlink(line, None, &config);
+        assert!(formatted.contains("https://example.com/dandavison/delta/issues/456"));
+    }
 }
@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// A git remote, parsed into the pieces needed to build web URLs (commit
+/// links, issue links, etc.) against the forge that hosts it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitRemoteRepo {
+    GitHubRepo(ParsedGitRemote),
+    GitLabRepo(ParsedGitRemote),
+    BitbucketRepo(ParsedGitRemote),
+    GiteaRepo(ParsedGitRemote),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedGitRemote {
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl GitRemoteRepo {
+    fn parsed(&self) -> &ParsedGitRemote {
+        match self {
+            GitRemoteRepo::GitHubRepo(r)
+            | GitRemoteRepo::GitLabRepo(r)
+            | GitRemoteRepo::BitbucketRepo(r)
+            | GitRemoteRepo::GiteaRepo(r) => r,
+        }
+    }
+
+    /// The `{base}/{owner}/{repo}` URL prefix for this remote, with the host
+    /// taken from the remote itself so self-hosted instances work. The
+    /// scheme is always `https`, whatever scheme the remote URL uses.
+    fn base_url(&self) -> String {
+        let ParsedGitRemote { host, owner, repo } = self.parsed();
+        format!("https://{}/{}/{}", host, owner, repo)
+    }
+
+    /// Build a URL to a commit on this remote's forge.
+    pub fn format_commit_url(&self, commit: &str) -> String {
+        match self {
+            GitRemoteRepo::GitHubRepo(_) | GitRemoteRepo::GiteaRepo(_) => {
+                format!("{}/commit/{}", self.base_url(), commit)
+            }
+            GitRemoteRepo::GitLabRepo(_) => format!("{}/-/commit/{}", self.base_url(), commit),
+            GitRemoteRepo::BitbucketRepo(_) => format!("{}/commits/{}", self.base_url(), commit),
+        }
+    }
+
+    /// Build a URL to issue/PR `number` in this remote's own repo.
+    pub fn format_issue_url(&self, number: &str) -> String {
+        let ParsedGitRemote { owner, repo, .. } = self.parsed();
+        self.format_issue_url_for(owner, repo, number)
+    }
+
+    /// Build a URL to issue/PR `number` in `owner/repo` on this remote's
+    /// forge, e.g. for a cross-repo reference like `owner/repo#123`.
+    pub fn format_issue_url_for(&self, owner: &str, repo: &str, number: &str) -> String {
+        let base = format!("https://{}/{}/{}", self.parsed().host, owner, repo);
+        match self {
+            GitRemoteRepo::GitHubRepo(_)
+            | GitRemoteRepo::GiteaRepo(_)
+            | GitRemoteRepo::BitbucketRepo(_) => format!("{}/issues/{}", base, number),
+            GitRemoteRepo::GitLabRepo(_) => format!("{}/-/issues/{}", base, number),
+        }
+    }
+
+    /// The `#L10-L25`-style anchor this forge uses to link to a range of
+    /// lines within a file, or `#L10` for a single line (`start == end`).
+    pub fn format_line_range_anchor(&self, start: usize, end: usize) -> String {
+        if start == end {
+            return format!("#L{}", start);
+        }
+        match self {
+            GitRemoteRepo::GitHubRepo(_)
+            | GitRemoteRepo::GiteaRepo(_)
+            | GitRemoteRepo::BitbucketRepo(_) => format!("#L{}-L{}", start, end),
+            GitRemoteRepo::GitLabRepo(_) => format!("#L{}-{}", start, end),
+        }
+    }
+}
+
+lazy_static! {
+    // https://host/owner/repo(.git)?
+    static ref HTTPS_REMOTE_URL: Regex =
+        Regex::new(r"^(?:https?|ssh)://(?:[^@/]+@)?([^/]+)/([^/]+)/(.+?)(?:\.git)?/?$").unwrap();
+    // git@host:owner/repo(.git)?
+    static ref SCP_REMOTE_URL: Regex =
+        Regex::new(r"^(?:[^@/]+@)?([^:/]+):([^/]+)/(.+?)(?:\.git)?/?$").unwrap();
+}
+
+impl FromStr for GitRemoteRepo {
+    type Err = ();
+
+    fn from_str(remote_url: &str) -> Result<Self, Self::Err> {
+        let captures = HTTPS_REMOTE_URL
+            .captures(remote_url)
+            .or_else(|| SCP_REMOTE_URL.captures(remote_url))
+            .ok_or(())?;
+        let host = captures.get(1).unwrap().as_str().to_string();
+        let owner = captures.get(2).unwrap().as_str().to_string();
+        let repo = captures.get(3).unwrap().as_str().to_string();
+        let parsed = ParsedGitRemote { host, owner, repo };
+        Ok(forge_for_host(&parsed.host)(parsed))
+    }
+}
+
+/// Determine the forge constructor to use for a given remote host. Hosts
+/// that don't match a known forge (e.g. a self-hosted GitHub Enterprise
+/// instance under a private domain) are assumed to be GitHub-compatible,
+/// matching delta's previous behavior.
+fn forge_for_host(host: &str) -> fn(ParsedGitRemote) -> GitRemoteRepo {
+    let host = host.to_lowercase();
+    if host.contains("gitlab") {
+        GitRemoteRepo::GitLabRepo
+    } else if host.contains("bitbucket") {
+        GitRemoteRepo::BitbucketRepo
+    } else if host.contains("gitea") || host.contains("codeberg") {
+        GitRemoteRepo::GiteaRepo
+    } else {
+        GitRemoteRepo::GitHubRepo
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitConfigEntry {
+    Path(PathBuf),
+    GitRemote(GitRemoteRepo),
+}
+
+/// Thin wrapper around the repository's git2 handle and an `enabled` flag.
+/// Resolved entries such as `delta.__workdir__` are not stored here.
+pub struct GitConfig {
+    pub repo: Option<git2::Repository>,
+    pub enabled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_github_https() {
+        let repo = GitRemoteRepo::from_str("https://github.com/dandavison/delta.git").unwrap();
+        assert_eq!(
+            repo,
+            GitRemoteRepo::GitHubRepo(ParsedGitRemote {
+                host: "github.com".into(),
+                owner: "dandavison".into(),
+                repo: "delta".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_str_github_ssh() {
+        let repo = GitRemoteRepo::from_str("git@github.com:dandavison/delta.git").unwrap();
+        assert_eq!(
+            repo,
+            GitRemoteRepo::GitHubRepo(ParsedGitRemote {
+                host: "github.com".into(),
+                owner: "dandavison".into(),
+                repo: "delta".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_str_gitlab() {
+        let repo = GitRemoteRepo::from_str("https://gitlab.com/owner/repo.git").unwrap();
+        assert!(matches!(repo, GitRemoteRepo::GitLabRepo(_)));
+        assert_eq!(
+            repo.format_commit_url("abc123"),
+            "https://gitlab.com/owner/repo/-/commit/abc123"
+        );
+    }
+
+    #[test]
+    fn test_from_str_bitbucket() {
+        let repo = GitRemoteRepo::from_str("git@bitbucket.org:owner/repo.git").unwrap();
+        assert_eq!(
+            repo.format_commit_url("abc123"),
+            "https://bitbucket.org/owner/repo/commits/abc123"
+        );
+    }
+
+    #[test]
+    fn test_from_str_self_hosted_unknown_host_defaults_to_github() {
+        let repo = GitRemoteRepo::from_str("ssh://git@git.example.com/owner/repo.git").unwrap();
+        assert_eq!(
+            repo.format_commit_url("abc123"),
+            "https://git.example.com/owner/repo/commit/abc123"
+        );
+        assert!(matches!(repo, GitRemoteRepo::GitHubRepo(_)));
+    }
+
+    #[test]
+    fn test_format_line_range_anchor() {
+        let github = GitRemoteRepo::from_str("https://github.com/owner/repo").unwrap();
+        assert_eq!(github.format_line_range_anchor(10, 10), "#L10");
+        assert_eq!(github.format_line_range_anchor(10, 25), "#L10-L25");
+
+        let gitlab = GitRemoteRepo::from_str("https://gitlab.com/owner/repo").unwrap();
+        assert_eq!(gitlab.format_line_range_anchor(10, 25), "#L10-25");
+    }
+
+    #[test]
+    fn test_from_str_codeberg() {
+        let repo = GitRemoteRepo::from_str("https://codeberg.org/owner/repo").unwrap();
+        assert_eq!(
+            repo.format_commit_url("abc123"),
+            "https://codeberg.org/owner/repo/commit/abc123"
+        );
+    }
+}